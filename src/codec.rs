@@ -0,0 +1,120 @@
+//! `tokio_util::codec` integration so nwd1 frames can be driven through a
+//! `Framed<T, Nwd1Codec>` `Sink`/`Stream` instead of the one-shot
+//! [`crate::send_frame`]/[`crate::recv_frame`] helpers.
+
+use bytes::{BufMut, BytesMut};
+use nwd1::{decode, encode, Frame, MAGIC};
+use std::io;
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::{HEADER_LEN, MAX_FRAME_LEN_HARD};
+
+/// Frames an `AsyncRead + AsyncWrite` as a stream of nwd1 [`Frame`]s.
+///
+/// Construct with [`Nwd1Codec::new`] and hand to `Framed::new(io, codec)`.
+pub struct Nwd1Codec {
+    soft_cap: usize,
+}
+
+impl Nwd1Codec {
+    /// Build a codec that rejects any frame whose `LEN` exceeds `soft_cap`
+    /// (frames above [`MAX_FRAME_LEN_HARD`] are rejected regardless).
+    pub fn new(soft_cap: usize) -> Self {
+        Self { soft_cap }
+    }
+}
+
+impl Decoder for Nwd1Codec {
+    type Item = Frame;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Frame>, io::Error> {
+        if src.len() < HEADER_LEN {
+            return Ok(None);
+        }
+
+        if &src[..4] != MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "nwd1 bad magic"));
+        }
+
+        let len = u32::from_be_bytes([src[4], src[5], src[6], src[7]]) as usize;
+
+        if len > self.soft_cap || len > MAX_FRAME_LEN_HARD {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "nwd1 frame too large",
+            ));
+        }
+
+        let total = HEADER_LEN + len;
+        if src.len() < total {
+            src.reserve(total - src.len());
+            return Ok(None);
+        }
+
+        let buf = src.split_to(total);
+        match decode(&buf.freeze()) {
+            Ok(frame) => Ok(Some(frame)),
+            Err(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "nwd1 decode error",
+            )),
+        }
+    }
+}
+
+impl Encoder<&Frame> for Nwd1Codec {
+    type Error = io::Error;
+
+    fn encode(&mut self, frame: &Frame, dst: &mut BytesMut) -> Result<(), io::Error> {
+        let data = encode(frame);
+        dst.reserve(data.len());
+        dst.put_slice(&data);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use futures::{SinkExt, StreamExt};
+    use netid64::NetId64;
+    use tokio_util::codec::Framed;
+
+    #[tokio::test]
+    async fn roundtrip_via_framed() {
+        let frame = Frame {
+            id: NetId64::make(1, 7, 42),
+            kind: 1,
+            ver: 1,
+            payload: Bytes::from_static(b"hello"),
+        };
+
+        let (a, b) = tokio::io::duplex(64 * 1024);
+        let mut tx = Framed::new(a, Nwd1Codec::new(crate::DEFAULT_FRAME_LEN_SOFT));
+        let mut rx = Framed::new(b, Nwd1Codec::new(crate::DEFAULT_FRAME_LEN_SOFT));
+
+        tx.send(&frame).await.expect("send ok");
+        let decoded = rx.next().await.expect("some").expect("io ok");
+
+        assert_eq!(decoded.id.raw(), frame.id.raw());
+        assert_eq!(decoded.kind, frame.kind);
+        assert_eq!(decoded.ver, frame.ver);
+        assert_eq!(decoded.payload, frame.payload);
+    }
+
+    #[test]
+    fn decode_rejects_bad_magic() {
+        let mut codec = Nwd1Codec::new(crate::DEFAULT_FRAME_LEN_SOFT);
+        let mut src = BytesMut::from(&b"XXXX\x00\x00\x00\x00"[..]);
+        assert!(codec.decode(&mut src).is_err());
+    }
+
+    #[test]
+    fn decode_waits_for_more_data() {
+        let mut codec = Nwd1Codec::new(crate::DEFAULT_FRAME_LEN_SOFT);
+        let mut src = BytesMut::from(&MAGIC[..]);
+        assert!(codec.decode(&mut src).unwrap().is_none());
+    }
+}
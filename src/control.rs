@@ -0,0 +1,218 @@
+//! Control frames for keepalive and graceful close.
+//!
+//! Long-lived browser sessions need liveness and an orderly shutdown, so
+//! this reserves a slice of the `kind` space for control traffic and
+//! layers a small helper over [`crate::recv_frame`]/[`crate::send_frame`]
+//! that answers `PING` with `PONG` transparently and surfaces `CLOSE` as
+//! a typed [`CloseReason`] instead of a decode error — the same shape as
+//! WebSocket close framing.
+
+use std::io;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use netid64::NetId64;
+use nwd1::Frame;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use crate::{recv_frame, send_frame};
+
+/// Reserved `kind` for a liveness probe; the peer must answer with [`KIND_PONG`].
+pub const KIND_PING: u8 = 0xF0;
+/// Reserved `kind` for a [`KIND_PING`] reply.
+pub const KIND_PONG: u8 = 0xF1;
+/// Reserved `kind` for a graceful close, carrying a [`CloseReason`].
+pub const KIND_CLOSE: u8 = 0xF2;
+
+/// Normal, expected closure.
+pub const CLOSE_NORMAL: u16 = 1000;
+/// Peer violated the framing protocol.
+pub const CLOSE_PROTOCOL_ERROR: u16 = 1002;
+/// Payload could not be decoded as expected.
+pub const CLOSE_INVALID_DATA: u16 = 1003;
+/// Frame exceeded the negotiated size limit.
+pub const CLOSE_TOO_LARGE: u16 = 1009;
+/// Catch-all for conditions with no more specific code.
+pub const CLOSE_UNEXPECTED: u16 = 1011;
+
+/// `NetId64` used for control frames, which aren't scoped to a particular
+/// network entity.
+fn control_id() -> NetId64 {
+    NetId64::make(0, 0, 0)
+}
+
+/// A `CLOSE` frame's payload: a status code plus an optional human-readable
+/// reason, modeled on WebSocket close framing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloseReason {
+    pub code: u16,
+    pub reason: String,
+}
+
+impl CloseReason {
+    fn decode(payload: &Bytes) -> io::Result<Self> {
+        if payload.len() < 2 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "nwd1 close frame missing status code",
+            ));
+        }
+        let code = u16::from_be_bytes([payload[0], payload[1]]);
+        let reason = String::from_utf8(payload[2..].to_vec()).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidData, "nwd1 close reason not utf8")
+        })?;
+        Ok(Self { code, reason })
+    }
+
+    fn encode(&self) -> Bytes {
+        let mut buf = BytesMut::with_capacity(2 + self.reason.len());
+        buf.put_u16(self.code);
+        buf.put_slice(self.reason.as_bytes());
+        buf.freeze()
+    }
+}
+
+/// The result of [`recv_controlled`]: either an application frame, or a
+/// `CLOSE` the peer sent (`PING`/`PONG` are handled transparently and
+/// never surface here).
+#[derive(Debug)]
+pub enum Received {
+    Frame(Frame),
+    Closed(CloseReason),
+}
+
+/// Like [`crate::recv_frame`], but answers `PING` with `PONG` without
+/// returning to the caller, and surfaces `CLOSE` as [`Received::Closed`]
+/// instead of an opaque decode error. `Ok(None)` on graceful EOF, same as
+/// `recv_frame`.
+pub async fn recv_controlled<RW: AsyncRead + AsyncWrite + Unpin>(
+    io: &mut RW,
+    soft_cap: usize,
+) -> io::Result<Option<Received>> {
+    loop {
+        let frame = match recv_frame(io, soft_cap).await? {
+            Some(frame) => frame,
+            None => return Ok(None),
+        };
+
+        match frame.kind {
+            KIND_PING => {
+                let pong = Frame {
+                    id: control_id(),
+                    kind: KIND_PONG,
+                    ver: frame.ver,
+                    payload: frame.payload,
+                };
+                send_frame(io, &pong).await?;
+            }
+            KIND_PONG => {
+                // Liveness reply only; nothing for the caller to act on.
+            }
+            KIND_CLOSE => {
+                return Ok(Some(Received::Closed(CloseReason::decode(&frame.payload)?)));
+            }
+            _ => return Ok(Some(Received::Frame(frame))),
+        }
+    }
+}
+
+/// Send a `PING` keepalive.
+pub async fn send_ping<W: AsyncWrite + Unpin>(writer: &mut W) -> io::Result<()> {
+    let frame = Frame {
+        id: control_id(),
+        kind: KIND_PING,
+        ver: 1,
+        payload: Bytes::new(),
+    };
+    send_frame(writer, &frame).await
+}
+
+/// Send a `CLOSE` frame carrying `code` and an optional UTF-8 `reason`.
+pub async fn send_close<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    code: u16,
+    reason: &str,
+) -> io::Result<()> {
+    let close = CloseReason {
+        code,
+        reason: reason.to_string(),
+    };
+    let frame = Frame {
+        id: control_id(),
+        kind: KIND_CLOSE,
+        ver: 1,
+        payload: close.encode(),
+    };
+    send_frame(writer, &frame).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn ping_is_answered_with_pong_transparently() {
+        let (mut a, mut b) = tokio::io::duplex(64 * 1024);
+
+        // `b` answers the ping inline, then keeps waiting for the next
+        // frame — which will be `a`'s close, once `a` has seen the pong.
+        let b_side = recv_controlled(&mut b, crate::DEFAULT_FRAME_LEN_SOFT);
+        let a_side = async {
+            send_ping(&mut a).await?;
+            let pong = recv_frame(&mut a, crate::DEFAULT_FRAME_LEN_SOFT)
+                .await?
+                .expect("pong frame");
+            assert_eq!(pong.kind, KIND_PONG);
+            send_close(&mut a, CLOSE_NORMAL, "done").await
+        };
+
+        let (b_result, a_result) = tokio::join!(b_side, a_side);
+        a_result.expect("a side ok");
+        match b_result.expect("io ok").expect("some") {
+            Received::Closed(reason) => assert_eq!(reason.code, CLOSE_NORMAL),
+            Received::Frame(_) => panic!("expected Closed"),
+        }
+    }
+
+    #[tokio::test]
+    async fn close_frame_surfaces_as_typed_reason() {
+        let (mut a, mut b) = tokio::io::duplex(64 * 1024);
+
+        send_close(&mut a, CLOSE_NORMAL, "bye").await.expect("send close");
+        let received = recv_controlled(&mut b, crate::DEFAULT_FRAME_LEN_SOFT)
+            .await
+            .expect("io ok")
+            .expect("some");
+
+        match received {
+            Received::Closed(reason) => {
+                assert_eq!(reason.code, CLOSE_NORMAL);
+                assert_eq!(reason.reason, "bye");
+            }
+            Received::Frame(_) => panic!("expected Closed"),
+        }
+    }
+
+    #[tokio::test]
+    async fn application_frame_passes_through() {
+        use bytes::Bytes as B;
+
+        let (mut a, mut b) = tokio::io::duplex(64 * 1024);
+        let app_frame = Frame {
+            id: NetId64::make(1, 7, 42),
+            kind: 1,
+            ver: 1,
+            payload: B::from_static(b"hello"),
+        };
+
+        send_frame(&mut a, &app_frame).await.expect("send ok");
+        let received = recv_controlled(&mut b, crate::DEFAULT_FRAME_LEN_SOFT)
+            .await
+            .expect("io ok")
+            .expect("some");
+
+        match received {
+            Received::Frame(f) => assert_eq!(f.payload, app_frame.payload),
+            Received::Closed(_) => panic!("expected Frame"),
+        }
+    }
+}
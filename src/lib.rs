@@ -22,11 +22,30 @@ use nwd1::{decode, encode, Frame, MAGIC};
 use std::io;
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 
+mod codec;
+pub use codec::Nwd1Codec;
+
+mod reader;
+pub use reader::FrameBodyReader;
+
+mod transport;
+pub use transport::{FramedTransport, InmemoryTransport, Reconnectable, Transport};
+
+mod send;
+pub use send::{send_frame_vectored, send_frames};
+
+mod control;
+pub use control::{
+    recv_controlled, send_close, send_ping, CloseReason, Received, CLOSE_INVALID_DATA,
+    CLOSE_NORMAL, CLOSE_PROTOCOL_ERROR, CLOSE_TOO_LARGE, CLOSE_UNEXPECTED, KIND_CLOSE, KIND_PING,
+    KIND_PONG,
+};
+
 /// Hard safety ceiling against pathological allocations (8 MiB)
 pub const MAX_FRAME_LEN_HARD: usize = 8 * 1024 * 1024;
 /// Reasonable default soft cap for browser-oriented traffic (256 KiB)
 pub const DEFAULT_FRAME_LEN_SOFT: usize = 256 * 1024;
-const HEADER_LEN: usize = 8; // MAGIC(4) + LEN(4)
+pub(crate) const HEADER_LEN: usize = 8; // MAGIC(4) + LEN(4)
 
 /// Send a single nwd1 frame over any Tokio `AsyncWrite`.
 pub async fn send_frame<W: AsyncWrite + Unpin>(writer: &mut W, frame: &Frame) -> io::Result<()> {
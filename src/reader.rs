@@ -0,0 +1,166 @@
+//! Streaming body reader so large frame payloads never need a full
+//! up-front `Vec` allocation — see [`FrameBodyReader`].
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use nwd1::MAGIC;
+use tokio::io::{AsyncRead, AsyncReadExt, ReadBuf};
+
+use crate::{HEADER_LEN, MAX_FRAME_LEN_HARD};
+
+/// Reads and validates an nwd1 header, then exposes the frame body as a
+/// plain [`AsyncRead`] of exactly `len` bytes — no intermediate `Vec`.
+///
+/// Callers can stream the body straight into a hasher, a file, or any
+/// other `AsyncWrite` via `tokio::io::copy` without ever materializing
+/// the whole payload in memory.
+pub struct FrameBodyReader<R> {
+    inner: R,
+    remaining: usize,
+}
+
+impl<R: AsyncRead + Unpin> FrameBodyReader<R> {
+    /// Read the 8-byte header from `inner`, enforce `soft_cap`/
+    /// [`MAX_FRAME_LEN_HARD`], and return a reader positioned at the
+    /// start of the body.
+    pub async fn new(mut inner: R, soft_cap: usize) -> io::Result<Self> {
+        let mut header = [0u8; HEADER_LEN];
+        inner.read_exact(&mut header).await?;
+
+        if &header[..4] != MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "nwd1 bad magic"));
+        }
+
+        let len = u32::from_be_bytes([header[4], header[5], header[6], header[7]]) as usize;
+
+        if len > soft_cap || len > MAX_FRAME_LEN_HARD {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "nwd1 frame too large",
+            ));
+        }
+
+        Ok(Self {
+            inner,
+            remaining: len,
+        })
+    }
+
+    /// Number of body bytes not yet read.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for FrameBodyReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        if self.remaining == 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        // Limit the inner read to exactly `take` bytes so we never read
+        // past the end of this frame's body into the next one.
+        let take = self.remaining.min(buf.remaining());
+        let mut limited = buf.take(take);
+        let ptr = limited.filled().as_ptr();
+
+        match Pin::new(&mut self.inner).poll_read(cx, &mut limited) {
+            Poll::Ready(Ok(())) => {
+                assert_eq!(limited.filled().as_ptr(), ptr);
+                let n = limited.filled().len();
+                // SAFETY: the inner reader filled, and so initialized, the
+                // first `n` bytes of `limited`, which are the first `n`
+                // unfilled bytes of `buf`.
+                unsafe {
+                    buf.assume_init(n);
+                }
+                buf.advance(n);
+
+                if n == 0 && self.remaining != 0 {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "nwd1 body truncated (unexpected EOF)",
+                    )));
+                }
+                self.remaining -= n;
+                Poll::Ready(Ok(()))
+            }
+            Poll::Ready(Err(e)) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "nwd1 body truncated (unexpected EOF)",
+                )))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nwd1::{encode, Frame};
+    use netid64::NetId64;
+    use bytes::Bytes;
+    use tokio::io::AsyncWriteExt;
+
+    #[tokio::test]
+    async fn streams_body_without_full_buffer() {
+        let frame = Frame {
+            id: NetId64::make(1, 7, 42),
+            kind: 1,
+            ver: 1,
+            payload: Bytes::from_static(b"hello world"),
+        };
+        let data = encode(&frame);
+        let expected_body = data[HEADER_LEN..].to_vec();
+
+        let (mut w, r) = tokio::io::duplex(64 * 1024);
+        let send = async move { w.write_all(&data).await };
+        let recv = async move {
+            let mut reader = FrameBodyReader::new(r, crate::DEFAULT_FRAME_LEN_SOFT).await?;
+            let mut out = Vec::new();
+            tokio::io::AsyncReadExt::read_to_end(&mut reader, &mut out).await?;
+            io::Result::Ok(out)
+        };
+
+        let (sw, rr) = tokio::join!(send, recv);
+        sw.expect("write ok");
+        assert_eq!(rr.expect("read ok"), expected_body);
+    }
+
+    #[tokio::test]
+    async fn errors_on_truncated_body() {
+        let frame = Frame {
+            id: NetId64::make(1, 7, 42),
+            kind: 1,
+            ver: 1,
+            payload: Bytes::from_static(b"hello world"),
+        };
+        let mut data = encode(&frame);
+        data.truncate(data.len() - 4);
+
+        let (mut w, r) = tokio::io::duplex(64 * 1024);
+        let send = async move {
+            w.write_all(&data).await?;
+            w.shutdown().await
+        };
+        let recv = async move {
+            let mut reader = FrameBodyReader::new(r, crate::DEFAULT_FRAME_LEN_SOFT).await?;
+            let mut out = Vec::new();
+            tokio::io::AsyncReadExt::read_to_end(&mut reader, &mut out).await?;
+            io::Result::Ok(out)
+        };
+
+        let (sw, rr) = tokio::join!(send, recv);
+        sw.expect("write ok");
+        assert!(rr.is_err());
+    }
+}
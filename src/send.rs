@@ -0,0 +1,155 @@
+//! Vectored, copy-free frame sending.
+//!
+//! [`crate::send_frame`] calls `nwd1::encode`, which allocates a fresh
+//! buffer and copies the whole payload into it before `write_all`. For
+//! large browser payloads that doubles memory traffic. The helpers here
+//! instead write the frame metadata (`MAGIC | LEN | ID | KIND | VER`) and
+//! the `Bytes` payload as separate [`IoSlice`]s via a vectored write, so
+//! the payload is handed to the writer without an intermediate copy —
+//! the same borrowed-slice approach deno's `BufView` uses for zero-copy
+//! sends.
+
+use std::io::{self, IoSlice};
+
+use nwd1::{Frame, MAGIC};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+/// Size of everything on the wire except the payload: `MAGIC(4) + LEN(4)
+/// + ID(8) + KIND(1) + VER(1)`.
+const META_LEN: usize = 4 + 4 + 8 + 1 + 1;
+
+/// Build the fixed-size `MAGIC | LEN | ID | KIND | VER` prefix for `frame`
+/// without touching its payload.
+fn meta_bytes(frame: &Frame) -> [u8; META_LEN] {
+    let mut meta = [0u8; META_LEN];
+    meta[..4].copy_from_slice(MAGIC);
+    let len = (META_LEN - 8 + frame.payload.len()) as u32; // ID + KIND + VER + PAYLOAD
+    meta[4..8].copy_from_slice(&len.to_be_bytes());
+    meta[8..16].copy_from_slice(&frame.id.raw().to_be_bytes());
+    meta[16] = frame.kind;
+    meta[17] = frame.ver;
+    meta
+}
+
+/// Send a single frame without copying its payload, using a vectored
+/// write of the metadata prefix followed by the payload bytes.
+pub async fn send_frame_vectored<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    frame: &Frame,
+) -> io::Result<()> {
+    let meta = meta_bytes(frame);
+    write_vectored_all(writer, &mut [IoSlice::new(&meta), IoSlice::new(&frame.payload)]).await
+}
+
+/// Send many frames with vectored writes, handing every frame's metadata
+/// and payload to `write_vectored` together to cut syscalls under load.
+/// Further `write_vectored` calls follow only when the writer accepts
+/// fewer bytes than were offered.
+pub async fn send_frames<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    frames: &[Frame],
+) -> io::Result<()> {
+    let metas: Vec<[u8; META_LEN]> = frames.iter().map(meta_bytes).collect();
+
+    let mut slices = Vec::with_capacity(frames.len() * 2);
+    for (meta, frame) in metas.iter().zip(frames) {
+        slices.push(IoSlice::new(meta));
+        slices.push(IoSlice::new(&frame.payload));
+    }
+
+    write_vectored_all(writer, &mut slices).await
+}
+
+/// `write_vectored` only guarantees *some* progress per call, so keep
+/// calling it — advancing past fully-written slices and re-slicing a
+/// partially-written one via [`IoSlice::advance_slices`] — until every
+/// byte has gone out.
+///
+/// `advance_slices` folds any trailing zero-length slice (an empty
+/// payload) into the slice it follows once that slice is fully written,
+/// so an empty-payload frame never produces a trailing `write_vectored`
+/// call with nothing left to write.
+async fn write_vectored_all<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    mut bufs: &mut [IoSlice<'_>],
+) -> io::Result<()> {
+    while !bufs.is_empty() {
+        let n = writer.write_vectored(bufs).await?;
+        if n == 0 {
+            return Err(io::Error::new(io::ErrorKind::WriteZero, "nwd1 write zero"));
+        }
+        IoSlice::advance_slices(&mut bufs, n);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use netid64::NetId64;
+
+    fn frame(kind: u8, payload: &'static [u8]) -> Frame {
+        Frame {
+            id: NetId64::make(1, 7, 42),
+            kind,
+            ver: 1,
+            payload: Bytes::from_static(payload),
+        }
+    }
+
+    #[tokio::test]
+    async fn send_frame_vectored_matches_recv_frame() {
+        let f = frame(1, b"hello");
+        let (mut w, mut r) = tokio::io::duplex(64 * 1024);
+
+        let send = async move { send_frame_vectored(&mut w, &f).await };
+        let recv = async move { crate::recv_frame(&mut r, crate::DEFAULT_FRAME_LEN_SOFT).await };
+
+        let (sw, rr) = tokio::join!(send, recv);
+        sw.expect("write ok");
+        let decoded = rr.expect("io ok").expect("some");
+        assert_eq!(decoded.payload, Bytes::from_static(b"hello"));
+    }
+
+    #[tokio::test]
+    async fn send_frame_vectored_handles_empty_payload() {
+        let f = frame(1, b"");
+        let (mut w, mut r) = tokio::io::duplex(64 * 1024);
+
+        let send = async move { send_frame_vectored(&mut w, &f).await };
+        let recv = async move { crate::recv_frame(&mut r, crate::DEFAULT_FRAME_LEN_SOFT).await };
+
+        let (sw, rr) = tokio::join!(send, recv);
+        sw.expect("write ok");
+        let decoded = rr.expect("io ok").expect("some");
+        assert_eq!(decoded.payload, Bytes::new());
+    }
+
+    #[tokio::test]
+    async fn send_frames_batches_multiple() {
+        let frames = vec![frame(1, b"one"), frame(2, b"two"), frame(3, b"three")];
+        let (mut w, mut r) = tokio::io::duplex(64 * 1024);
+
+        let send = async { send_frames(&mut w, &frames).await };
+        let recv = async move {
+            let mut out = Vec::new();
+            for _ in 0..3 {
+                out.push(
+                    crate::recv_frame(&mut r, crate::DEFAULT_FRAME_LEN_SOFT)
+                        .await
+                        .expect("io ok")
+                        .expect("some"),
+                );
+            }
+            out
+        };
+
+        let (sw, decoded) = tokio::join!(send, recv);
+        sw.expect("write ok");
+        for (d, f) in decoded.iter().zip(frames.iter()) {
+            assert_eq!(d.payload, f.payload);
+            assert_eq!(d.kind, f.kind);
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! Transport abstraction over nwd1 framing.
+//!
+//! Where [`crate::send_frame`]/[`crate::recv_frame`] are free functions tied
+//! to a concrete `AsyncRead`/`AsyncWrite` pair, [`Transport`] lets callers
+//! hold a single handle that can be read from and written to, and
+//! [`Reconnectable`] lets that handle survive a dropped connection without
+//! losing the frame that was in flight.
+
+use std::io;
+
+use nwd1::Frame;
+use tokio::io::{duplex, AsyncRead, AsyncWrite, DuplexStream};
+
+use crate::{recv_frame, send_frame, DEFAULT_FRAME_LEN_SOFT};
+
+/// A connection that speaks nwd1 frames.
+// The futures returned by these `async fn`s carry no `Send` bound, and the
+// trait is not usable as a `dyn` object.
+#[allow(async_fn_in_trait)]
+pub trait Transport {
+    /// Receive the next frame, or `Ok(None)` on graceful EOF.
+    async fn read_frame(&mut self) -> io::Result<Option<Frame>>;
+
+    /// Send a single frame.
+    async fn write_frame(&mut self, frame: &Frame) -> io::Result<()>;
+}
+
+/// A connection that knows how to re-establish itself after it drops.
+/// Implemented by the stream inside a [`FramedTransport`]; it does not
+/// require [`Transport`].
+#[allow(async_fn_in_trait)]
+pub trait Reconnectable {
+    /// Re-establish the connection in place.
+    async fn reconnect(&mut self) -> io::Result<()>;
+}
+
+/// Adapts any `AsyncRead + AsyncWrite` into a [`Transport`].
+///
+/// [`Transport::write_frame`] itself is a thin pass-through to
+/// [`crate::send_frame`] and does not retry. When the inner stream is
+/// also [`Reconnectable`], use [`write_frame_resilient`](Self::write_frame_resilient)
+/// instead: on a mid-frame `BrokenPipe`/`ConnectionReset` it calls
+/// [`reconnect`](Reconnectable::reconnect) and replays the frame once,
+/// instead of silently dropping it.
+pub struct FramedTransport<T> {
+    io: T,
+    soft_cap: usize,
+}
+
+impl<T> FramedTransport<T> {
+    /// Wrap `io` with the default soft frame-length cap.
+    pub fn new(io: T) -> Self {
+        Self::with_soft_cap(io, DEFAULT_FRAME_LEN_SOFT)
+    }
+
+    /// Wrap `io`, rejecting any frame whose `LEN` exceeds `soft_cap`.
+    pub fn with_soft_cap(io: T, soft_cap: usize) -> Self {
+        Self { io, soft_cap }
+    }
+
+    /// Borrow the underlying transport.
+    pub fn get_ref(&self) -> &T {
+        &self.io
+    }
+}
+
+fn is_reconnectable_error(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::BrokenPipe | io::ErrorKind::ConnectionReset
+    )
+}
+
+impl<T: AsyncRead + AsyncWrite + Unpin> Transport for FramedTransport<T> {
+    async fn read_frame(&mut self) -> io::Result<Option<Frame>> {
+        recv_frame(&mut self.io, self.soft_cap).await
+    }
+
+    async fn write_frame(&mut self, frame: &Frame) -> io::Result<()> {
+        send_frame(&mut self.io, frame).await
+    }
+}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Reconnectable> FramedTransport<T> {
+    /// Like [`Transport::write_frame`], but on a mid-frame `BrokenPipe`/
+    /// `ConnectionReset` reconnects the inner transport and replays the
+    /// frame once before giving up.
+    pub async fn write_frame_resilient(&mut self, frame: &Frame) -> io::Result<()> {
+        match send_frame(&mut self.io, frame).await {
+            Ok(()) => Ok(()),
+            Err(e) if is_reconnectable_error(&e) => {
+                self.io.reconnect().await?;
+                send_frame(&mut self.io, frame).await
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// In-memory [`Transport`] backed by [`tokio::io::duplex`], for tests and
+/// local loopback where a real socket would be overkill.
+pub struct InmemoryTransport {
+    io: DuplexStream,
+    soft_cap: usize,
+}
+
+impl InmemoryTransport {
+    /// Build a connected pair of in-memory transports with `max_buf_size`
+    /// bytes of internal buffering and the default soft frame-length cap.
+    pub fn pair(max_buf_size: usize) -> (Self, Self) {
+        let (a, b) = duplex(max_buf_size);
+        (
+            Self {
+                io: a,
+                soft_cap: DEFAULT_FRAME_LEN_SOFT,
+            },
+            Self {
+                io: b,
+                soft_cap: DEFAULT_FRAME_LEN_SOFT,
+            },
+        )
+    }
+}
+
+impl Transport for InmemoryTransport {
+    async fn read_frame(&mut self) -> io::Result<Option<Frame>> {
+        recv_frame(&mut self.io, self.soft_cap).await
+    }
+
+    async fn write_frame(&mut self, frame: &Frame) -> io::Result<()> {
+        send_frame(&mut self.io, frame).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use netid64::NetId64;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    /// A `DuplexStream` wrapper whose first write fails with a
+    /// reconnectable error; later writes pass through, and `reconnect`
+    /// only records that it was called.
+    struct FlakyIo {
+        inner: DuplexStream,
+        fail_next_write: bool,
+        reconnected: bool,
+    }
+
+    impl AsyncRead for FlakyIo {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut tokio::io::ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.inner).poll_read(cx, buf)
+        }
+    }
+
+    impl AsyncWrite for FlakyIo {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            if self.fail_next_write {
+                self.fail_next_write = false;
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::BrokenPipe,
+                    "simulated broken pipe",
+                )));
+            }
+            Pin::new(&mut self.inner).poll_write(cx, buf)
+        }
+
+        fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.inner).poll_flush(cx)
+        }
+
+        fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.inner).poll_shutdown(cx)
+        }
+    }
+
+    impl Reconnectable for FlakyIo {
+        async fn reconnect(&mut self) -> io::Result<()> {
+            self.reconnected = true;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn inmemory_transport_roundtrip() {
+        let (mut a, mut b) = InmemoryTransport::pair(64 * 1024);
+        let frame = Frame {
+            id: NetId64::make(1, 7, 42),
+            kind: 1,
+            ver: 1,
+            payload: Bytes::from_static(b"hello"),
+        };
+
+        a.write_frame(&frame).await.expect("write ok");
+        let decoded = b.read_frame().await.expect("io ok").expect("some");
+
+        assert_eq!(decoded.id.raw(), frame.id.raw());
+        assert_eq!(decoded.kind, frame.kind);
+        assert_eq!(decoded.ver, frame.ver);
+        assert_eq!(decoded.payload, frame.payload);
+    }
+
+    #[tokio::test]
+    async fn framed_transport_roundtrip() {
+        let (a, b) = tokio::io::duplex(64 * 1024);
+        let mut tx = FramedTransport::new(a);
+        let mut rx = FramedTransport::new(b);
+        let frame = Frame {
+            id: NetId64::make(1, 7, 43),
+            kind: 2,
+            ver: 1,
+            payload: Bytes::from_static(b"world"),
+        };
+
+        tx.write_frame(&frame).await.expect("write ok");
+        let decoded = rx.read_frame().await.expect("io ok").expect("some");
+
+        assert_eq!(decoded.payload, frame.payload);
+    }
+
+    #[tokio::test]
+    async fn write_frame_resilient_reconnects_and_replays() {
+        let (a, mut peer) = tokio::io::duplex(64 * 1024);
+        let flaky = FlakyIo {
+            inner: a,
+            fail_next_write: true,
+            reconnected: false,
+        };
+        let mut tx = FramedTransport::new(flaky);
+        let frame = Frame {
+            id: NetId64::make(1, 7, 44),
+            kind: 5,
+            ver: 1,
+            payload: Bytes::from_static(b"retry me"),
+        };
+
+        tx.write_frame_resilient(&frame)
+            .await
+            .expect("resilient write ok");
+        assert!(tx.get_ref().reconnected, "reconnect should have been called");
+
+        let decoded = recv_frame(&mut peer, DEFAULT_FRAME_LEN_SOFT)
+            .await
+            .expect("io ok")
+            .expect("some");
+        assert_eq!(decoded.payload, frame.payload);
+    }
+}